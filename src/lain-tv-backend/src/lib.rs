@@ -1,16 +1,20 @@
 use candid::{CandidType, Deserialize, Principal};
 use ic_cdk::management_canister::{
-    http_request, HttpRequestArgs, HttpMethod,
+    http_request, transform_context_from_query, HttpMethod, HttpRequestArgs, HttpRequestResult,
+    TransformArgs,
 };
 use ic_cdk::{api::canister_self, api::msg_caller, query, update};
 use ic_cdk_timers::{clear_timer, set_timer, TimerId};
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
-use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, Storable};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell, Storable};
 use serde::{Serialize};
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::ops::Bound;
 use std::time::Duration;
 
+mod rss;
+
 // Type aliases
 type Memory = VirtualMemory<DefaultMemoryImpl>;
 type VideoId = String;
@@ -59,8 +63,330 @@ pub struct Stats {
     pub last_poll: Option<Timestamp>,
 }
 
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum SortOrder {
+    NewestFirst,
+    OldestFirst,
+    TitleAsc,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ListParams {
+    pub limit: u64,
+    pub continuation: Option<String>,
+    pub order: SortOrder,
+    pub channel: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Paginator<T> {
+    pub items: Vec<T>,
+    pub continuation: Option<String>,
+    pub total: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Changes {
+    pub updated: Vec<Video>,
+    pub removed: Vec<VideoId>,
+    pub as_of: Timestamp,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+struct ChangeLogEntry {
+    video_id: VideoId,
+    changed_at: Timestamp,
+}
+
+// `Timestamp` (`i64`) has no built-in `Storable` impl, so removal times
+// are wrapped before going into the tombstone map.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+struct Tombstone {
+    removed_at: Timestamp,
+}
+
+// Secondary index keys for `list_videos_paged`: each orders videos by a
+// sort field with the video id as a tiebreaker, so `StableBTreeMap::range`
+// can resume a scan right after the last-returned video in O(log n + k)
+// instead of loading and sorting the whole catalog per page.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct PublishedKey {
+    published_at: Timestamp,
+    id: VideoId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct TitleKey {
+    title: String,
+    id: VideoId,
+}
+
+// Per-channel counterparts of `PublishedKey`/`TitleKey`, so a channel-scoped
+// page (`ListParams.channel`) can range-scan just that channel's block of
+// the index instead of filtering the whole catalog. `channel` sorts first,
+// so every video for a channel occupies one contiguous key range.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct ChannelPublishedKey {
+    channel: String,
+    published_at: Timestamp,
+    id: VideoId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct ChannelTitleKey {
+    channel: String,
+    title: String,
+    id: VideoId,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub enum LiveStatus {
+    Offline,
+    Live { started_at: Timestamp, viewers: u64 },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Serialize)]
+pub struct ChannelSubscription {
+    pub channel_id: String,
+    pub channel_name: String,
+    pub rss_url: Url,
+    pub added_at: Timestamp,
+}
+
+// Odysee `claim_search` proxy response shapes, used only to deserialize the
+// HTTP outcall body before mapping into our own `Video` type.
+#[derive(Deserialize, Serialize, Debug)]
+struct ClaimSearchResponse {
+    result: ClaimSearchResult,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ClaimSearchResult {
+    items: Vec<ClaimItem>,
+    #[allow(dead_code)]
+    page: u32,
+    #[allow(dead_code)]
+    total_pages: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ClaimItem {
+    claim_id: String,
+    name: String,
+    signing_channel: Option<SigningChannel>,
+    value: ClaimValue,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct SigningChannel {
+    name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct ClaimValue {
+    title: Option<String>,
+    description: Option<String>,
+    thumbnail: Option<Thumbnail>,
+    release_time: Option<i64>,
+    license: Option<String>,
+    #[serde(default)]
+    is_live: Option<bool>,
+    #[serde(default)]
+    viewer_count: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct Thumbnail {
+    url: String,
+}
+
+// Current on-disk encoding of `Video`. Bump this and add a
+// `decode_video` match arm (backed by a `migrate_vN_to_vN+1` step) any
+// time a field is added or renamed, so existing stable-memory rows
+// upgrade in place instead of panicking on decode after the next upgrade.
+const CURRENT_VIDEO_SCHEMA_VERSION: u16 = 1;
+
 // Implement Storable for Video
 impl Storable for Video {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let mut bytes = CURRENT_VIDEO_SCHEMA_VERSION.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&serde_json::to_vec(self).unwrap());
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        // Rows written before this versioning scheme existed have no
+        // prefix at all - just bare JSON, which always starts with `{`
+        // (0x7B). A real version tag's low byte is the version number
+        // itself, so any tag in `1..=CURRENT_VIDEO_SCHEMA_VERSION` can
+        // never collide with a legacy row's leading byte. Forward the
+        // *actual* tag on to `decode_video` rather than collapsing every
+        // non-current version into "legacy" - that would misparse a
+        // genuinely older tagged version (e.g. v1 data read after a
+        // bump to v2) as unprefixed bytes instead of routing it through
+        // its own migration step.
+        if bytes.len() >= 2 {
+            let version = u16::from_le_bytes([bytes[0], bytes[1]]);
+            if (1..=CURRENT_VIDEO_SCHEMA_VERSION).contains(&version) {
+                return decode_video(version, &bytes[2..]);
+            }
+        }
+        decode_video(0, &bytes)
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+// Pre-series on-disk encoding of `Video`: bare JSON with no version
+// prefix, from before this schema versioning scheme existed. Fields added
+// since then default rather than fail to decode.
+#[derive(Deserialize)]
+struct VideoV0 {
+    id: VideoId,
+    title: String,
+    description: String,
+    channel: String,
+    odysee_url: Url,
+    thumbnail_url: Option<Url>,
+    published_at: Timestamp,
+    fetched_at: Timestamp,
+    #[serde(default)]
+    content_hash: Option<String>,
+    fetch_status: FetchStatus,
+    #[serde(default)]
+    license: Option<String>,
+}
+
+fn migrate_v0_to_v1(legacy: VideoV0) -> Video {
+    Video {
+        id: legacy.id,
+        title: legacy.title,
+        description: legacy.description,
+        channel: legacy.channel,
+        odysee_url: legacy.odysee_url,
+        thumbnail_url: legacy.thumbnail_url,
+        published_at: legacy.published_at,
+        fetched_at: legacy.fetched_at,
+        content_hash: legacy.content_hash,
+        fetch_status: legacy.fetch_status,
+        license: legacy.license,
+    }
+}
+
+// Stepwise schema migration for `Video`. Each past schema version gets its
+// own arm here that upgrades the old encoding (filling new fields with
+// defaults) before handing it on to the next version's migration, ending
+// at the current struct.
+//
+// Arms are keyed by their literal version number, not by
+// `CURRENT_VIDEO_SCHEMA_VERSION` - if the last arm matched on that constant
+// instead, bumping it to add a new version would silently repoint that arm
+// at the new version and orphan the version that used to be current, which
+// would then fall through to the `other` panic instead of decoding. Bumping
+// `CURRENT_VIDEO_SCHEMA_VERSION` must come with both a new `VideoVN` struct
+// (if the shape changed) and a new arm here keyed by the version number
+// that used to be current, e.g. `1 => migrate_v1_to_v2(...)`.
+fn decode_video(version: u16, body: &[u8]) -> Video {
+    match version {
+        0 => migrate_v0_to_v1(serde_json::from_slice(body).unwrap()),
+        1 => serde_json::from_slice(body).unwrap(),
+        other => panic!("unsupported Video schema version {other}: no migration path registered"),
+    }
+}
+
+// Implement Storable for ChannelSubscription
+impl Storable for ChannelSubscription {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(serde_json::to_vec(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+// Implement Storable for PublishedKey
+impl Storable for PublishedKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(serde_json::to_vec(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+// Implement Storable for TitleKey
+impl Storable for TitleKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(serde_json::to_vec(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+// Implement Storable for ChannelPublishedKey
+impl Storable for ChannelPublishedKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(serde_json::to_vec(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+// Implement Storable for ChannelTitleKey
+impl Storable for ChannelTitleKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(serde_json::to_vec(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+// Implement Storable for LiveStatus
+impl Storable for LiveStatus {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(serde_json::to_vec(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+// Implement Storable for ChangeLogEntry
+impl Storable for ChangeLogEntry {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(serde_json::to_vec(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+// Implement Storable for Tombstone
+impl Storable for Tombstone {
     fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Owned(serde_json::to_vec(self).unwrap())
     }
@@ -83,15 +409,79 @@ thread_local! {
         )
     );
 
-    static POLL_CONFIG: RefCell<PollConfig> = RefCell::new(PollConfig {
-        interval_seconds: 86400, // 24 hours default
-        enabled: false,
-    });
+    static POLL_CONFIG: RefCell<PollConfig> = const {
+        RefCell::new(PollConfig {
+            interval_seconds: 86400, // 24 hours default
+            enabled: false,
+        })
+    };
+
+    static LAST_POLL: RefCell<Option<Timestamp>> = const { RefCell::new(None) };
+    static POLL_TIMER_ID: RefCell<Option<TimerId>> = const { RefCell::new(None) };
 
-    static LAST_POLL: RefCell<Option<Timestamp>> = RefCell::new(None);
-    static POLL_TIMER_ID: RefCell<Option<TimerId>> = RefCell::new(None);
+    static CHANNELS: RefCell<StableBTreeMap<String, ChannelSubscription, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(1))),
+        )
+    );
+
+    static LIVE_STATUS: RefCell<StableBTreeMap<String, LiveStatus, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(2))),
+        )
+    );
+
+    static CHANGE_LOG: RefCell<StableBTreeMap<u64, ChangeLogEntry, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(3))),
+        )
+    );
+
+    static TOMBSTONES: RefCell<StableBTreeMap<VideoId, Tombstone, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(4))),
+        )
+    );
+
+    static CHANGE_SEQ: RefCell<u64> = const { RefCell::new(0) };
+
+    static VIDEO_SCHEMA_VERSION: RefCell<StableCell<u16, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(5))),
+            CURRENT_VIDEO_SCHEMA_VERSION,
+        ).expect("failed to init schema version cell")
+    );
+
+    static VIDEOS_BY_PUBLISHED: RefCell<StableBTreeMap<PublishedKey, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(6))),
+        )
+    );
+
+    static VIDEOS_BY_TITLE: RefCell<StableBTreeMap<TitleKey, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(7))),
+        )
+    );
+
+    static VIDEOS_BY_CHANNEL_PUBLISHED: RefCell<StableBTreeMap<ChannelPublishedKey, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(8))),
+        )
+    );
+
+    static VIDEOS_BY_CHANNEL_TITLE: RefCell<StableBTreeMap<ChannelTitleKey, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(9))),
+        )
+    );
 }
 
+// How long a change-log entry or removal tombstone is kept before
+// `get_changes_since` stops reporting it. Clients that fall further
+// behind than this must re-fetch the whole catalog via `list_videos_paged`.
+const CHANGE_LOG_RETENTION_MS: Timestamp = 7 * 24 * 60 * 60 * 1000;
+
 // Helper functions
 fn get_current_time() -> Timestamp {
     ic_cdk::api::time() as i64 / 1_000_000 // Convert nanoseconds to milliseconds
@@ -103,6 +493,184 @@ fn is_admin(caller: Principal) -> bool {
     caller == canister_self() || caller.to_text().contains("rdmx6-jaaaa")
 }
 
+// Insert or overwrite a video, keeping the `published_at`/title secondary
+// indexes that back `list_videos_paged` in sync with the primary store.
+fn upsert_video(video: Video) {
+    let previous = VIDEOS.with(|videos| videos.borrow().get(&video.id));
+    VIDEOS.with(|videos| {
+        videos.borrow_mut().insert(video.id.clone(), video.clone());
+    });
+    if let Some(previous) = &previous {
+        deindex_video(previous);
+    }
+    index_video(&video);
+}
+
+// Remove a video and its index entries together.
+fn delete_video(id: &VideoId) -> Option<Video> {
+    let removed = VIDEOS.with(|videos| videos.borrow_mut().remove(id));
+    if let Some(video) = &removed {
+        deindex_video(video);
+    }
+    removed
+}
+
+fn index_video(video: &Video) {
+    VIDEOS_BY_PUBLISHED.with(|index| {
+        index.borrow_mut().insert(
+            PublishedKey {
+                published_at: video.published_at,
+                id: video.id.clone(),
+            },
+            (),
+        );
+    });
+    VIDEOS_BY_TITLE.with(|index| {
+        index.borrow_mut().insert(
+            TitleKey {
+                title: video.title.clone(),
+                id: video.id.clone(),
+            },
+            (),
+        );
+    });
+    VIDEOS_BY_CHANNEL_PUBLISHED.with(|index| {
+        index.borrow_mut().insert(
+            ChannelPublishedKey {
+                channel: video.channel.clone(),
+                published_at: video.published_at,
+                id: video.id.clone(),
+            },
+            (),
+        );
+    });
+    VIDEOS_BY_CHANNEL_TITLE.with(|index| {
+        index.borrow_mut().insert(
+            ChannelTitleKey {
+                channel: video.channel.clone(),
+                title: video.title.clone(),
+                id: video.id.clone(),
+            },
+            (),
+        );
+    });
+}
+
+fn deindex_video(video: &Video) {
+    VIDEOS_BY_PUBLISHED.with(|index| {
+        index.borrow_mut().remove(&PublishedKey {
+            published_at: video.published_at,
+            id: video.id.clone(),
+        });
+    });
+    VIDEOS_BY_TITLE.with(|index| {
+        index.borrow_mut().remove(&TitleKey {
+            title: video.title.clone(),
+            id: video.id.clone(),
+        });
+    });
+    VIDEOS_BY_CHANNEL_PUBLISHED.with(|index| {
+        index.borrow_mut().remove(&ChannelPublishedKey {
+            channel: video.channel.clone(),
+            published_at: video.published_at,
+            id: video.id.clone(),
+        });
+    });
+    VIDEOS_BY_CHANNEL_TITLE.with(|index| {
+        index.borrow_mut().remove(&ChannelTitleKey {
+            channel: video.channel.clone(),
+            title: video.title.clone(),
+            id: video.id.clone(),
+        });
+    });
+}
+
+// Rebuild the secondary indexes from scratch. Safe to call unconditionally
+// on upgrade: a no-op once the indexes are populated, and the only way to
+// backfill them for a store that predates this indexing scheme.
+fn reindex_all_videos_if_needed() {
+    let indexed = VIDEOS_BY_PUBLISHED.with(|index| !index.borrow().is_empty());
+    let has_videos = VIDEOS.with(|videos| !videos.borrow().is_empty());
+    if indexed || !has_videos {
+        return;
+    }
+    VIDEOS.with(|videos| {
+        for (_, video) in videos.borrow().iter() {
+            index_video(&video);
+        }
+    });
+}
+
+// Exclusive upper bound for every channel-scoped key whose `channel`
+// field equals `channel`: the channel name with a trailing NUL, which
+// sorts after every key whose `channel` is exactly `channel` and before
+// any lexicographically-greater channel (including longer names that
+// start with `channel`), bounding a range scan to exactly this channel's
+// block of the index.
+fn channel_upper_bound(channel: &str) -> String {
+    format!("{channel}\0")
+}
+
+// Delta-sync change tracking
+fn record_change(video_id: &str, changed_at: Timestamp) {
+    let seq = CHANGE_SEQ.with(|seq| {
+        let mut seq = seq.borrow_mut();
+        *seq += 1;
+        *seq
+    });
+
+    CHANGE_LOG.with(|log| {
+        log.borrow_mut().insert(
+            seq,
+            ChangeLogEntry {
+                video_id: video_id.to_string(),
+                changed_at,
+            },
+        );
+    });
+}
+
+fn record_removal(video_id: &str, removed_at: Timestamp) {
+    TOMBSTONES.with(|tombstones| {
+        tombstones
+            .borrow_mut()
+            .insert(video_id.to_string(), Tombstone { removed_at });
+    });
+}
+
+fn prune_change_log(now: Timestamp) {
+    let cutoff = now - CHANGE_LOG_RETENTION_MS;
+
+    let stale_seqs: Vec<u64> = CHANGE_LOG.with(|log| {
+        log.borrow()
+            .iter()
+            .filter(|(_, entry)| entry.changed_at < cutoff)
+            .map(|(seq, _)| seq)
+            .collect()
+    });
+    CHANGE_LOG.with(|log| {
+        let mut log = log.borrow_mut();
+        for seq in stale_seqs {
+            log.remove(&seq);
+        }
+    });
+
+    let stale_tombstones: Vec<VideoId> = TOMBSTONES.with(|tombstones| {
+        tombstones
+            .borrow()
+            .iter()
+            .filter(|(_, tombstone)| tombstone.removed_at < cutoff)
+            .map(|(id, _)| id)
+            .collect()
+    });
+    TOMBSTONES.with(|tombstones| {
+        let mut tombstones = tombstones.borrow_mut();
+        for id in stale_tombstones {
+            tombstones.remove(&id);
+        }
+    });
+}
+
 // Video management functions
 #[query]
 fn list_videos() -> Vec<Video> {
@@ -120,6 +688,232 @@ fn get_video(id: VideoId) -> Option<Video> {
     VIDEOS.with(|videos| videos.borrow().get(&id))
 }
 
+// Resumable page of `list_videos`, optionally scoped to one channel via
+// `ListParams.channel`. The continuation token is the id of the last item
+// returned; it's resolved back to its sort key so the scan can resume
+// with a bounded `StableBTreeMap::range` (over a channel-scoped index
+// when `channel` is set) instead of re-sorting and re-walking the whole
+// catalog on every page.
+#[query]
+fn list_videos_paged(params: ListParams) -> Paginator<Video> {
+    let limit = params.limit.max(1) as usize;
+    let after: Option<Video> = params
+        .continuation
+        .as_ref()
+        .and_then(|id| VIDEOS.with(|videos| videos.borrow().get(id)));
+
+    let mut ids: Vec<VideoId> = match (&params.order, &params.channel) {
+        (SortOrder::OldestFirst, None) => {
+            let lower = after.as_ref().map_or(Bound::Unbounded, |video| {
+                Bound::Excluded(PublishedKey {
+                    published_at: video.published_at,
+                    id: video.id.clone(),
+                })
+            });
+            VIDEOS_BY_PUBLISHED.with(|index| {
+                index
+                    .borrow()
+                    .range((lower, Bound::Unbounded))
+                    .take(limit + 1)
+                    .map(|(key, _)| key.id)
+                    .collect()
+            })
+        }
+        (SortOrder::NewestFirst, None) => {
+            let upper = after.as_ref().map_or(Bound::Unbounded, |video| {
+                Bound::Excluded(PublishedKey {
+                    published_at: video.published_at,
+                    id: video.id.clone(),
+                })
+            });
+            VIDEOS_BY_PUBLISHED.with(|index| {
+                index
+                    .borrow()
+                    .range((Bound::Unbounded, upper))
+                    .rev()
+                    .take(limit + 1)
+                    .map(|(key, _)| key.id)
+                    .collect()
+            })
+        }
+        (SortOrder::TitleAsc, None) => {
+            let lower = after.as_ref().map_or(Bound::Unbounded, |video| {
+                Bound::Excluded(TitleKey {
+                    title: video.title.clone(),
+                    id: video.id.clone(),
+                })
+            });
+            VIDEOS_BY_TITLE.with(|index| {
+                index
+                    .borrow()
+                    .range((lower, Bound::Unbounded))
+                    .take(limit + 1)
+                    .map(|(key, _)| key.id)
+                    .collect()
+            })
+        }
+        (SortOrder::OldestFirst, Some(channel)) => {
+            let lower = after.as_ref().map_or(
+                Bound::Included(ChannelPublishedKey {
+                    channel: channel.clone(),
+                    published_at: Timestamp::MIN,
+                    id: String::new(),
+                }),
+                |video| {
+                    Bound::Excluded(ChannelPublishedKey {
+                        channel: channel.clone(),
+                        published_at: video.published_at,
+                        id: video.id.clone(),
+                    })
+                },
+            );
+            let upper = Bound::Excluded(ChannelPublishedKey {
+                channel: channel_upper_bound(channel),
+                published_at: Timestamp::MIN,
+                id: String::new(),
+            });
+            VIDEOS_BY_CHANNEL_PUBLISHED.with(|index| {
+                index
+                    .borrow()
+                    .range((lower, upper))
+                    .take(limit + 1)
+                    .map(|(key, _)| key.id)
+                    .collect()
+            })
+        }
+        (SortOrder::NewestFirst, Some(channel)) => {
+            let lower = Bound::Included(ChannelPublishedKey {
+                channel: channel.clone(),
+                published_at: Timestamp::MIN,
+                id: String::new(),
+            });
+            let upper = after.as_ref().map_or(
+                Bound::Excluded(ChannelPublishedKey {
+                    channel: channel_upper_bound(channel),
+                    published_at: Timestamp::MIN,
+                    id: String::new(),
+                }),
+                |video| {
+                    Bound::Excluded(ChannelPublishedKey {
+                        channel: channel.clone(),
+                        published_at: video.published_at,
+                        id: video.id.clone(),
+                    })
+                },
+            );
+            VIDEOS_BY_CHANNEL_PUBLISHED.with(|index| {
+                index
+                    .borrow()
+                    .range((lower, upper))
+                    .rev()
+                    .take(limit + 1)
+                    .map(|(key, _)| key.id)
+                    .collect()
+            })
+        }
+        (SortOrder::TitleAsc, Some(channel)) => {
+            let lower = after.as_ref().map_or(
+                Bound::Included(ChannelTitleKey {
+                    channel: channel.clone(),
+                    title: String::new(),
+                    id: String::new(),
+                }),
+                |video| {
+                    Bound::Excluded(ChannelTitleKey {
+                        channel: channel.clone(),
+                        title: video.title.clone(),
+                        id: video.id.clone(),
+                    })
+                },
+            );
+            let upper = Bound::Excluded(ChannelTitleKey {
+                channel: channel_upper_bound(channel),
+                title: String::new(),
+                id: String::new(),
+            });
+            VIDEOS_BY_CHANNEL_TITLE.with(|index| {
+                index
+                    .borrow()
+                    .range((lower, upper))
+                    .take(limit + 1)
+                    .map(|(key, _)| key.id)
+                    .collect()
+            })
+        }
+    };
+
+    let has_more = ids.len() > limit;
+    ids.truncate(limit);
+    let continuation = has_more.then(|| ids.last().cloned()).flatten();
+
+    let items: Vec<Video> =
+        VIDEOS.with(|videos| ids.iter().filter_map(|id| videos.borrow().get(id)).collect());
+
+    let total = match &params.channel {
+        Some(channel) => {
+            let lower = Bound::Included(ChannelPublishedKey {
+                channel: channel.clone(),
+                published_at: Timestamp::MIN,
+                id: String::new(),
+            });
+            let upper = Bound::Excluded(ChannelPublishedKey {
+                channel: channel_upper_bound(channel),
+                published_at: Timestamp::MIN,
+                id: String::new(),
+            });
+            VIDEOS_BY_CHANNEL_PUBLISHED.with(|index| index.borrow().range((lower, upper)).count()) as u64
+        }
+        None => VIDEOS.with(|videos| videos.borrow().len()),
+    };
+
+    Paginator {
+        items,
+        continuation,
+        total,
+    }
+}
+
+// Returns just what changed since `since` instead of the whole catalog,
+// so bandwidth stays proportional to churn rather than catalog size.
+#[query]
+fn get_changes_since(since: Timestamp) -> Changes {
+    let as_of = get_current_time();
+
+    let changed_ids: std::collections::HashSet<VideoId> = CHANGE_LOG.with(|log| {
+        log.borrow()
+            .iter()
+            .filter(|(_, entry)| entry.changed_at > since)
+            .map(|(_, entry)| entry.video_id)
+            .collect()
+    });
+
+    let updated: Vec<Video> = VIDEOS.with(|videos| {
+        let videos = videos.borrow();
+        changed_ids.iter().filter_map(|id| videos.get(id)).collect()
+    });
+
+    let removed: Vec<VideoId> = TOMBSTONES.with(|tombstones| {
+        tombstones
+            .borrow()
+            .iter()
+            .filter(|(_, tombstone)| tombstone.removed_at > since)
+            .map(|(id, _)| id)
+            .collect()
+    });
+
+    Changes {
+        updated,
+        removed,
+        as_of,
+    }
+}
+
+// Hard cap on a single `get_videos_by_channel` response so one call can't
+// grow past the outbound response-size limit; channels with more videos
+// than this should page through `list_videos_paged` with
+// `ListParams.channel` set instead, which isn't capped.
+const MAX_CHANNEL_RESULTS: usize = 500;
+
 #[query]
 fn get_videos_by_channel(channel: String) -> Vec<Video> {
     VIDEOS.with(|videos| {
@@ -128,6 +922,7 @@ fn get_videos_by_channel(channel: String) -> Vec<Video> {
             .iter()
             .filter(|(_, video)| video.channel.to_lowercase() == channel.to_lowercase())
             .map(|(_, video)| video)
+            .take(MAX_CHANNEL_RESULTS)
             .collect()
     })
 }
@@ -141,38 +936,133 @@ fn add_or_update_video(video: Video) -> Result_ {
     updated_video.fetched_at = get_current_time();
     
     // For demo, allow anyone to add videos. In production, restrict to admin
-    VIDEOS.with(|videos| {
-        videos.borrow_mut().insert(updated_video.id.clone(), updated_video);
-        Result_::Ok
-    })
+    let video_id = updated_video.id.clone();
+    let changed_at = updated_video.fetched_at;
+    upsert_video(updated_video);
+    record_change(&video_id, changed_at);
+
+    Result_::Ok
 }
 
 #[update]
 fn remove_video(id: VideoId) -> Result_ {
     let caller_principal = msg_caller();
-    
+
     if !is_admin(caller_principal) {
         return Result_::Err("Access denied: admin required".to_string());
     }
-    
-    VIDEOS.with(|videos| {
-        match videos.borrow_mut().remove(&id) {
+
+    match delete_video(&id) {
+        Some(_) => {
+            record_removal(&id, get_current_time());
+            Result_::Ok
+        }
+        None => Result_::Err("Video not found".to_string()),
+    }
+}
+
+// Channel subscription management
+#[update]
+fn add_channel(channel_id: String, channel_name: String) -> Result_ {
+    let caller_principal = msg_caller();
+
+    if !is_admin(caller_principal) {
+        return Result_::Err("Access denied: admin required".to_string());
+    }
+
+    let subscription = ChannelSubscription {
+        channel_id: channel_id.clone(),
+        channel_name: channel_name.clone(),
+        rss_url: format!("https://odysee.com/$/rss/@{}", channel_name),
+        added_at: get_current_time(),
+    };
+
+    CHANNELS.with(|channels| {
+        channels.borrow_mut().insert(channel_id, subscription);
+    });
+
+    Result_::Ok
+}
+
+#[update]
+fn remove_channel(channel_id: String) -> Result_ {
+    let caller_principal = msg_caller();
+
+    if !is_admin(caller_principal) {
+        return Result_::Err("Access denied: admin required".to_string());
+    }
+
+    CHANNELS.with(|channels| {
+        match channels.borrow_mut().remove(&channel_id) {
             Some(_) => Result_::Ok,
-            None => Result_::Err("Video not found".to_string()),
+            None => Result_::Err("Channel not found".to_string()),
         }
     })
 }
 
+#[query]
+fn list_channels() -> Vec<ChannelSubscription> {
+    CHANNELS.with(|channels| {
+        channels
+            .borrow()
+            .iter()
+            .map(|(_, sub)| sub)
+            .collect()
+    })
+}
+
+// Live-stream status
+#[query]
+fn get_live_status(channel: String) -> LiveStatus {
+    LIVE_STATUS.with(|live| live.borrow().get(&channel).unwrap_or(LiveStatus::Offline))
+}
+
+#[query]
+fn get_live_channels() -> Vec<String> {
+    LIVE_STATUS.with(|live| {
+        live.borrow()
+            .iter()
+            .filter(|(_, status)| matches!(status, LiveStatus::Live { .. }))
+            .map(|(channel, _)| channel)
+            .collect()
+    })
+}
+
+// Record a poll's live/offline observation for a channel. Viewer counts
+// refresh on every poll without disturbing `started_at`, so uptime stays
+// accurate across polls that only see viewer churn.
+fn update_live_status(channel: &str, is_live: bool, viewers: u64) {
+    LIVE_STATUS.with(|live| {
+        let mut live = live.borrow_mut();
+        let new_status = if is_live {
+            match live.get(&channel.to_string()) {
+                Some(LiveStatus::Live { started_at, .. }) => LiveStatus::Live { started_at, viewers },
+                _ => LiveStatus::Live {
+                    started_at: get_current_time(),
+                    viewers,
+                },
+            }
+        } else {
+            LiveStatus::Offline
+        };
+        live.insert(channel.to_string(), new_status);
+    });
+}
+
 // Polling functionality
 #[update]
 async fn manual_poll() -> Result_ {
     let caller_principal = msg_caller();
-    
+
     if !is_admin(caller_principal) {
         return Result_::Err("Access denied: admin required".to_string());
     }
-    
-    match perform_odysee_fetch().await {
+
+    let odysee_result = perform_odysee_fetch().await;
+    let _ = perform_channel_polls().await;
+    prune_change_log(get_current_time());
+
+    match odysee_result {
         Ok(_count) => {
             LAST_POLL.with(|last_poll| {
                 *last_poll.borrow_mut() = Some(get_current_time());
@@ -209,6 +1099,8 @@ fn set_poll_config(config: PollConfig) -> Result_ {
         let timer_id = set_timer(duration, || {
             ic_cdk::futures::spawn(async {
                 let _ = perform_odysee_fetch().await;
+                let _ = perform_channel_polls().await;
+                prune_change_log(get_current_time());
                 LAST_POLL.with(|last_poll| {
                     *last_poll.borrow_mut() = Some(get_current_time());
                 });
@@ -228,37 +1120,104 @@ fn get_poll_config() -> PollConfig {
     POLL_CONFIG.with(|config| config.borrow().clone())
 }
 
+// Strip non-deterministic headers from the Odysee outcall response so the
+// outcall can reach consensus. When the body parses, it is re-serialized
+// through our own `ClaimSearchResponse` model, which drops any fields we
+// don't parse (e.g. server time) along the way; otherwise it is passed
+// through unchanged. The live-stream fields `is_live` and `viewer_count`
+// are parsed, so they are kept in the canonical body.
+#[query]
+fn transform_odysee_response(args: TransformArgs) -> HttpRequestResult {
+    let mut response = args.response;
+    response.headers.clear();
+
+    if let Ok(parsed) = serde_json::from_slice::<ClaimSearchResponse>(&response.body) {
+        if let Ok(canonical) = serde_json::to_vec(&parsed) {
+            response.body = canonical;
+        }
+    }
+
+    response
+}
+
+// Strip non-deterministic headers from an RSS/Atom feed outcall so every
+// replica sees byte-identical output. Unlike `transform_odysee_response`,
+// the body is feed XML, not a `ClaimSearchResponse`, so it's passed
+// through untouched rather than re-serialized through a JSON model.
+#[query]
+fn transform_channel_feed(args: TransformArgs) -> HttpRequestResult {
+    let mut response = args.response;
+    response.headers.clear();
+    response
+}
+
 // HTTP outcall to Odysee
 async fn perform_odysee_fetch() -> std::result::Result<usize, String> {
-    // Example: Fetch from Odysee API claim_search endpoint
-    // This is a simplified example - in production you'd want more robust error handling
-    
     let url = "https://api.odysee.com/api/v1/proxy?method=claim_search&page_size=20&order_by=trending_mixed";
-    
+
     let request = HttpRequestArgs {
         url: url.to_string(),
         method: HttpMethod::GET,
         body: None,
         max_response_bytes: Some(10_000), // 10KB limit
-        transform: None, // Simplified for now
+        transform: Some(transform_context_from_query(
+            "transform_odysee_response".to_string(),
+            vec![],
+        )),
         headers: vec![],
     };
-    
+
     match http_request(&request).await {
         Ok(response_result) => {
             if response_result.status == 200u64 {
-                // Parse response and update videos
-                // This is a simplified mock - real implementation would parse JSON
-                let mock_videos = create_mock_videos();
-                let count = mock_videos.len();
-                
-                VIDEOS.with(|videos| {
-                    let mut videos_map = videos.borrow_mut();
-                    for video in mock_videos {
-                        videos_map.insert(video.id.clone(), video);
+                let parsed: ClaimSearchResponse = serde_json::from_slice(&response_result.body)
+                    .map_err(|e| format!("Failed to parse Odysee response: {}", e))?;
+
+                let fetched_at = get_current_time();
+                let mut videos = Vec::with_capacity(parsed.result.items.len());
+                let mut live_updates = Vec::new();
+
+                for item in parsed.result.items {
+                    if let Some(signing_channel) = &item.signing_channel {
+                        live_updates.push((
+                            signing_channel.name.clone(),
+                            item.value.is_live.unwrap_or(false),
+                            item.value.viewer_count.unwrap_or(0),
+                        ));
                     }
+                    videos.push(video_from_claim(item, fetched_at));
+                }
+
+                let count = videos.len();
+
+                for video in videos {
+                    let video_id = video.id.clone();
+                    upsert_video(video);
+                    record_change(&video_id, fetched_at);
+                }
+
+                let seen_channels: std::collections::HashSet<String> =
+                    live_updates.iter().map(|(channel, _, _)| channel.clone()).collect();
+                for (channel, is_live, viewers) in live_updates {
+                    update_live_status(&channel, is_live, viewers);
+                }
+
+                // A channel that no longer shows up in this poll's results
+                // at all (rather than showing up with is_live = false) is
+                // treated the same as a stream going down.
+                let stale_live: Vec<String> = LIVE_STATUS.with(|live| {
+                    live.borrow()
+                        .iter()
+                        .filter(|(channel, status)| {
+                            matches!(status, LiveStatus::Live { .. }) && !seen_channels.contains(channel)
+                        })
+                        .map(|(channel, _)| channel)
+                        .collect()
                 });
-                
+                for channel in stale_live {
+                    update_live_status(&channel, false, 0);
+                }
+
                 Ok(count)
             } else {
                 Err(format!("HTTP error: {}", response_result.status))
@@ -268,6 +1227,93 @@ async fn perform_odysee_fetch() -> std::result::Result<usize, String> {
     }
 }
 
+// Map a single `claim_search` result item into our `Video` model, flagging
+// `fetch_status` as `Error` rather than dropping the row when a field we
+// need (channel name or title) is absent from the claim.
+fn video_from_claim(item: ClaimItem, fetched_at: Timestamp) -> Video {
+    let channel = item.signing_channel.map(|c| c.name);
+
+    let (fetch_status, title) = match (&channel, &item.value.title) {
+        (Some(_), Some(title)) => (FetchStatus::Ok, title.clone()),
+        (None, _) => (
+            FetchStatus::Error("missing signing_channel".to_string()),
+            item.value.title.clone().unwrap_or_default(),
+        ),
+        (Some(_), None) => (
+            FetchStatus::Error("missing title".to_string()),
+            String::new(),
+        ),
+    };
+
+    let odysee_url = match &channel {
+        Some(channel) => format!("https://odysee.com/@{}/{}", channel, item.name),
+        None => format!("https://odysee.com/{}", item.name),
+    };
+
+    Video {
+        id: item.claim_id.clone(),
+        title,
+        description: item.value.description.unwrap_or_default(),
+        channel: channel.unwrap_or_default(),
+        odysee_url,
+        thumbnail_url: item.value.thumbnail.map(|t| t.url),
+        published_at: item.value.release_time.map(|secs| secs * 1000).unwrap_or(0),
+        fetched_at,
+        content_hash: Some(item.claim_id),
+        fetch_status,
+        license: item.value.license,
+    }
+}
+
+// Fetch each subscribed channel's RSS feed and merge any new entries into
+// the video store. RSS is cheaper and more stable than the JSON
+// claim_search proxy for per-channel updates, so it runs alongside it
+// rather than replacing it.
+async fn perform_channel_polls() -> usize {
+    let subscriptions: Vec<ChannelSubscription> =
+        CHANNELS.with(|channels| channels.borrow().iter().map(|(_, sub)| sub).collect());
+
+    let mut total = 0;
+    for subscription in subscriptions {
+        let request = HttpRequestArgs {
+            url: subscription.rss_url.clone(),
+            method: HttpMethod::GET,
+            body: None,
+            max_response_bytes: Some(50_000),
+            transform: Some(transform_context_from_query(
+                "transform_channel_feed".to_string(),
+                vec![],
+            )),
+            headers: vec![],
+        };
+
+        let Ok(response) = http_request(&request).await else {
+            continue;
+        };
+        if response.status != 200u64 {
+            continue;
+        }
+
+        total += ingest_channel_feed(&response.body, &subscription.channel_name);
+    }
+
+    total
+}
+
+fn ingest_channel_feed(body: &[u8], channel_name: &str) -> usize {
+    let fetched_at = get_current_time();
+    let videos = rss::parse_channel_feed(body, channel_name, fetched_at);
+    let count = videos.len();
+
+    for video in videos {
+        let video_id = video.id.clone();
+        upsert_video(video);
+        record_change(&video_id, fetched_at);
+    }
+
+    count
+}
+
 // Create mock videos for testing
 fn create_mock_videos() -> Vec<Video> {
     let current_time = get_current_time();
@@ -320,6 +1366,11 @@ fn whoami() -> Principal {
     msg_caller()
 }
 
+#[query]
+fn get_schema_version() -> u16 {
+    VIDEO_SCHEMA_VERSION.with(|version| *version.borrow().get())
+}
+
 #[query]
 fn get_stats() -> Stats {
     let total_videos = VIDEOS.with(|videos| videos.borrow().len() as u64);
@@ -335,14 +1386,20 @@ fn get_stats() -> Stats {
 #[ic_cdk::init]
 fn init() {
     // Add some initial mock videos
-    let mock_videos = create_mock_videos();
-    
-    VIDEOS.with(|videos| {
-        let mut videos_map = videos.borrow_mut();
-        for video in mock_videos {
-            videos_map.insert(video.id.clone(), video);
-        }
+    for video in create_mock_videos() {
+        upsert_video(video);
+    }
+}
+
+// Record the current schema version on every upgrade, so
+// `get_schema_version` always reflects the encoding this canister build
+// expects rather than whatever version happened to be stored last.
+#[ic_cdk::post_upgrade]
+fn post_upgrade() {
+    VIDEO_SCHEMA_VERSION.with(|version| {
+        let _ = version.borrow_mut().set(CURRENT_VIDEO_SCHEMA_VERSION);
     });
+    reindex_all_videos_if_needed();
 }
 
 // Export candid interface
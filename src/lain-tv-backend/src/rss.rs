@@ -0,0 +1,229 @@
+// RSS/Atom ingestion for subscribed Odysee channels, run alongside the
+// JSON claim_search proxy to pick up per-channel updates it misses.
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+
+use crate::{FetchStatus, Timestamp, Video};
+
+/// Parse an Odysee channel feed (`https://odysee.com/$/rss/@channel`) into
+/// `Video` rows. Handles both Atom `<entry>` and RSS `<item>` elements
+/// since Odysee's feed format varies by endpoint version.
+pub fn parse_channel_feed(body: &[u8], channel_name: &str, fetched_at: Timestamp) -> Vec<Video> {
+    let mut reader = Reader::from_reader(body);
+    reader.config_mut().trim_text(true);
+
+    let mut videos = Vec::new();
+    let mut buf = Vec::new();
+
+    let mut in_entry = false;
+    let mut current_tag = String::new();
+    let mut title = String::new();
+    let mut link = String::new();
+    let mut guid = String::new();
+    let mut published = String::new();
+    let mut thumbnail = None;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(tag)) | Ok(Event::Empty(tag)) => {
+                let name = String::from_utf8_lossy(tag.name().as_ref()).to_string();
+
+                if name == "entry" || name == "item" {
+                    in_entry = true;
+                    title.clear();
+                    link.clear();
+                    guid.clear();
+                    published.clear();
+                    thumbnail = None;
+                } else if in_entry && (name == "media:thumbnail" || name == "thumbnail") {
+                    thumbnail = tag
+                        .attributes()
+                        .flatten()
+                        .find(|attr| attr.key.as_ref() == b"url")
+                        .map(|attr| String::from_utf8_lossy(&attr.value).to_string());
+                } else if in_entry && name == "link" && link.is_empty() {
+                    // Atom carries the link as `<link href="..."/>`; RSS
+                    // puts it in the element text, read below.
+                    if let Some(href) = tag.attributes().flatten().find(|attr| attr.key.as_ref() == b"href") {
+                        link = String::from_utf8_lossy(&href.value).to_string();
+                    }
+                }
+
+                current_tag = name;
+            }
+            Ok(Event::Text(text)) if in_entry => {
+                let text = text.unescape().unwrap_or_default().into_owned();
+                match current_tag.as_str() {
+                    "title" => title.push_str(&text),
+                    "link" => link.push_str(&text),
+                    "id" | "guid" => guid.push_str(&text),
+                    "published" | "pubDate" => published.push_str(&text),
+                    _ => {}
+                }
+            }
+            Ok(Event::End(tag)) => {
+                let name = String::from_utf8_lossy(tag.name().as_ref()).to_string();
+                if (name == "entry" || name == "item") && in_entry {
+                    in_entry = false;
+                    // `link` is normally what we use as both the video id
+                    // and the playback URL; fall back to the entry's
+                    // `<id>`/`<guid>` when it's missing so a malformed
+                    // `<link>` doesn't drop the entry outright. If neither
+                    // is present we have nothing unique to key on - an
+                    // empty id would collide with, and silently overwrite,
+                    // any earlier such entry from this same poll, so skip
+                    // it instead of trusting it unconditionally.
+                    let id = if !link.trim().is_empty() {
+                        link.clone()
+                    } else {
+                        guid.clone()
+                    };
+                    if !id.trim().is_empty() {
+                        videos.push(Video {
+                            id,
+                            title: title.clone(),
+                            description: String::new(),
+                            channel: channel_name.to_string(),
+                            odysee_url: link.clone(),
+                            thumbnail_url: thumbnail.clone(),
+                            published_at: parse_published(&published),
+                            fetched_at,
+                            content_hash: None,
+                            fetch_status: FetchStatus::Ok,
+                            license: None,
+                        });
+                    }
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    videos
+}
+
+// Odysee feeds use RFC 3339 for Atom `<published>` and RFC 2822 for RSS
+// `<pubDate>`; falls back to 0 (obviously unset) if neither parses rather
+// than guessing "now".
+fn parse_published(raw: &str) -> Timestamp {
+    let raw = raw.trim();
+    parse_rfc3339(raw).or_else(|| parse_rfc2822(raw)).unwrap_or(0)
+}
+
+// `2024-01-15T10:30:00Z` / `2024-01-15T10:30:00.123+02:00`
+fn parse_rfc3339(raw: &str) -> Option<Timestamp> {
+    if raw.len() < 19 {
+        return None;
+    }
+    let year: i64 = raw.get(0..4)?.parse().ok()?;
+    let month: u32 = raw.get(5..7)?.parse().ok()?;
+    let day: u32 = raw.get(8..10)?.parse().ok()?;
+    let hour: i64 = raw.get(11..13)?.parse().ok()?;
+    let minute: i64 = raw.get(14..16)?.parse().ok()?;
+    let second: i64 = raw.get(17..19)?.parse().ok()?;
+    if !matches!(raw.as_bytes().get(4), Some(b'-'))
+        || !matches!(raw.as_bytes().get(7), Some(b'-'))
+        || !matches!(raw.as_bytes().get(13), Some(b':'))
+        || !matches!(raw.as_bytes().get(16), Some(b':'))
+    {
+        return None;
+    }
+
+    let offset_minutes = parse_numeric_offset(raw[19..].trim_start_matches(|c: char| c == '.' || c.is_ascii_digit()));
+    epoch_millis(year, month, day, hour, minute, second, offset_minutes)
+}
+
+// `Mon, 15 Jan 2024 10:30:00 GMT` (the leading day-of-week is optional)
+fn parse_rfc2822(raw: &str) -> Option<Timestamp> {
+    let mut parts: Vec<&str> = raw.split_whitespace().collect();
+    if parts.first().is_some_and(|p| p.ends_with(',')) {
+        parts.remove(0);
+    }
+    let [day, month, year, time, ..] = parts[..] else {
+        return None;
+    };
+
+    let day: u32 = day.parse().ok()?;
+    let month = month_from_name(month)?;
+    let year: i64 = year.parse().ok()?;
+    let mut time_parts = time.split(':');
+    let hour: i64 = time_parts.next()?.parse().ok()?;
+    let minute: i64 = time_parts.next()?.parse().ok()?;
+    let second: i64 = time_parts.next().unwrap_or("0").parse().ok()?;
+    let offset_minutes = parts.get(4).map_or(0, |tz| named_or_numeric_offset(tz));
+
+    epoch_millis(year, month, day, hour, minute, second, offset_minutes)
+}
+
+fn month_from_name(name: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let prefix = name.get(..3)?;
+    MONTHS
+        .iter()
+        .position(|m| m.eq_ignore_ascii_case(prefix))
+        .map(|index| index as u32 + 1)
+}
+
+fn named_or_numeric_offset(tz: &str) -> i64 {
+    match tz {
+        "UT" | "GMT" | "Z" => 0,
+        "EST" => -5 * 60,
+        "EDT" => -4 * 60,
+        "CST" => -6 * 60,
+        "CDT" => -5 * 60,
+        "MST" => -7 * 60,
+        "MDT" => -6 * 60,
+        "PST" => -8 * 60,
+        "PDT" => -7 * 60,
+        other => parse_numeric_offset(other),
+    }
+}
+
+// `+0000` / `-0500` / `+02:00`; anything unrecognized is treated as UTC.
+fn parse_numeric_offset(tz: &str) -> i64 {
+    let sign = match tz.as_bytes().first() {
+        Some(b'+') => 1,
+        Some(b'-') => -1,
+        _ => return 0,
+    };
+    let digits: String = tz[1..].chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.len() < 4 {
+        return 0;
+    }
+    let hours: i64 = digits[0..2].parse().unwrap_or(0);
+    let minutes: i64 = digits[2..4].parse().unwrap_or(0);
+    sign * (hours * 60 + minutes)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn epoch_millis(
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: i64,
+    minute: i64,
+    second: i64,
+    offset_minutes: i64,
+) -> Option<Timestamp> {
+    let days = days_from_civil(year, month, day);
+    let millis = days * 86_400_000 + hour * 3_600_000 + minute * 60_000 + second * 1000;
+    Some(millis - offset_minutes * 60_000)
+}
+
+// Howard Hinnant's `days_from_civil`: days since the Unix epoch for a
+// proleptic-Gregorian (year, month, day), used instead of pulling in a
+// date/time crate for this one conversion.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let year_of_era = y - era * 400;
+    let month_shifted = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_shifted + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}